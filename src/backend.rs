@@ -0,0 +1,100 @@
+//! Abstraction over the concrete drawing surface. A [`RenderBackend`] exposes
+//! a retained-mode API — meshes are registered once and referred to by an
+//! opaque [`MeshHandle`] — so callers render against the trait without knowing
+//! whether the pixels come from the scanline rasterizer or the path tracer.
+
+use crate::camera;
+use crate::math::{Mat4, Vec3, Vec4};
+use crate::raytracer::Material;
+use crate::renderer::Renderer;
+
+/// An opaque index into a backend's own mesh storage. Registering a mesh lets
+/// the backend pre-transform or cache geometry (e.g. build a BVH once) and
+/// instance the same handle with different model matrices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MeshHandle(pub usize);
+
+/// A rendering surface driven in retained mode.
+pub trait RenderBackend {
+    /// Clears the colour surface to `color`.
+    fn clear(&mut self, color: &Vec4);
+
+    /// Marks the start of a frame (e.g. clearing the depth buffer).
+    fn begin_frame(&mut self);
+
+    /// Marks the end of a frame, flushing any deferred work.
+    fn end_frame(&mut self);
+
+    /// Uploads a triangle mesh and returns a handle referring to it.
+    fn register_mesh(&mut self, vertices: &[Vec3], indices: &[u32]) -> MeshHandle;
+
+    /// Draws a previously registered mesh with the given model transform and
+    /// material.
+    fn draw_mesh(&mut self, mesh: MeshHandle, model: &Mat4, material: &dyn Material);
+
+    /// The rendered RGBA image.
+    fn rendered_image(&self) -> &[u8];
+}
+
+/// Backend-owned copy of a registered mesh.
+struct Mesh {
+    vertices: Vec<Vec3>,
+    indices: Vec<u32>,
+}
+
+/// The scanline rasterizer wrapped as a [`RenderBackend`]. Geometry is stored
+/// on registration and re-transformed each `draw_mesh` through [`Renderer`].
+pub struct RasterBackend {
+    renderer: Renderer,
+    meshes: Vec<Mesh>,
+}
+
+impl RasterBackend {
+    pub fn new(w: u32, h: u32, camera: camera::Camera) -> Self {
+        Self {
+            renderer: Renderer::new(w, h, camera),
+            meshes: Vec::new(),
+        }
+    }
+}
+
+impl RenderBackend for RasterBackend {
+    fn clear(&mut self, color: &Vec4) {
+        self.renderer.clear(color);
+    }
+
+    fn begin_frame(&mut self) {
+        // Reset depth so hidden-surface removal starts fresh each frame.
+        self.renderer.clear_depth(f32::MAX);
+    }
+
+    fn end_frame(&mut self) {}
+
+    fn register_mesh(&mut self, vertices: &[Vec3], indices: &[u32]) -> MeshHandle {
+        let handle = MeshHandle(self.meshes.len());
+        self.meshes.push(Mesh {
+            vertices: vertices.to_vec(),
+            indices: indices.to_vec(),
+        });
+        handle
+    }
+
+    fn draw_mesh(&mut self, mesh: MeshHandle, model: &Mat4, material: &dyn Material) {
+        let mesh = &self.meshes[mesh.0];
+        // The flat rasterizer shades each triangle with the material's base
+        // colour; the full BRDF is only evaluated by the path-traced backend.
+        let color = material.base_color();
+        for tri in mesh.indices.chunks_exact(3) {
+            let triangle = [
+                mesh.vertices[tri[0] as usize],
+                mesh.vertices[tri[1] as usize],
+                mesh.vertices[tri[2] as usize],
+            ];
+            self.renderer.draw_triangle(model, &triangle, &color);
+        }
+    }
+
+    fn rendered_image(&self) -> &[u8] {
+        self.renderer.get_rendered_image()
+    }
+}
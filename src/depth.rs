@@ -0,0 +1,73 @@
+/// Comparison applied between an incoming fragment's depth and the value
+/// already stored in the [`DepthAttachment`]. A fragment is only drawn when
+/// the test passes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepthFunc {
+    /// Pass when the incoming depth is strictly nearer than the stored one.
+    Less,
+    /// Pass when the incoming depth is nearer than or equal to the stored one.
+    LessEqual,
+    /// Always pass, ignoring the stored value (depth testing disabled).
+    Always,
+}
+
+impl Default for DepthFunc {
+    fn default() -> Self {
+        DepthFunc::Less
+    }
+}
+
+impl DepthFunc {
+    /// Returns whether a fragment with depth `incoming` passes against the
+    /// `stored` value under this function.
+    pub fn test(&self, incoming: f32, stored: f32) -> bool {
+        match self {
+            DepthFunc::Less => incoming < stored,
+            DepthFunc::LessEqual => incoming <= stored,
+            DepthFunc::Always => true,
+        }
+    }
+}
+
+/// A per-pixel depth buffer sitting alongside the
+/// [`ColorAttachment`](crate::image::ColorAttachment). Depth is stored in NDC
+/// space, so smaller values are nearer the camera.
+pub struct DepthAttachment {
+    width: u32,
+    height: u32,
+    data: Vec<f32>,
+}
+
+impl DepthAttachment {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![f32::MAX; (width * height) as usize],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Resets every pixel to `depth`, mirroring
+    /// [`ColorAttachment::clear`](crate::image::ColorAttachment::clear).
+    pub fn clear(&mut self, depth: f32) {
+        for d in &mut self.data {
+            *d = depth;
+        }
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> f32 {
+        self.data[(y * self.width + x) as usize]
+    }
+
+    pub fn set(&mut self, x: u32, y: u32, depth: f32) {
+        self.data[(y * self.width + x) as usize] = depth;
+    }
+}
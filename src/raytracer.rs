@@ -0,0 +1,651 @@
+//! An offline Monte-Carlo path tracer that shades triangle soup, as an
+//! alternative to the scanline [`Renderer`](crate::renderer::Renderer). It
+//! consumes the same triangle/material scene data and writes its result into a
+//! [`ColorAttachment`](crate::image::ColorAttachment), so callers can pick
+//! rasterized or ray-traced output from one scene.
+
+use crate::image::ColorAttachment;
+use crate::math::{Vec3, Vec4};
+
+// --- small vector helpers -------------------------------------------------
+// The path tracer leans on a handful of operations (dot, cross, reflect, …)
+// that it keeps local so it only depends on `Vec3`'s field access and `new`.
+
+fn dot(a: &Vec3, b: &Vec3) -> f32 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
+fn cross(a: &Vec3, b: &Vec3) -> Vec3 {
+    Vec3::new(
+        a.y * b.z - a.z * b.y,
+        a.z * b.x - a.x * b.z,
+        a.x * b.y - a.y * b.x,
+    )
+}
+
+fn length(v: &Vec3) -> f32 {
+    dot(v, v).sqrt()
+}
+
+fn normalize(v: &Vec3) -> Vec3 {
+    let len = length(v);
+    Vec3::new(v.x / len, v.y / len, v.z / len)
+}
+
+/// Component-wise product, used to attenuate radiance by a surface albedo.
+fn mul(a: &Vec3, b: &Vec3) -> Vec3 {
+    Vec3::new(a.x * b.x, a.y * b.y, a.z * b.z)
+}
+
+fn reflect(v: &Vec3, n: &Vec3) -> Vec3 {
+    *v - *n * (2.0 * dot(v, n))
+}
+
+fn refract(uv: &Vec3, n: &Vec3, etai_over_etat: f32) -> Vec3 {
+    let cos_theta = (-dot(uv, n)).min(1.0);
+    let r_out_perp = (*uv + *n * cos_theta) * etai_over_etat;
+    let r_out_parallel = *n * -(1.0 - dot(&r_out_perp, &r_out_perp)).abs().sqrt();
+    r_out_perp + r_out_parallel
+}
+
+/// A tiny xorshift64* generator so the tracer needs no external RNG crate.
+/// Exposed because it appears in the public [`Material::scatter`] signature.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self {
+            state: seed | 1,
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// A uniform sample in `[0, 1)`.
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u32 << 24) as f32
+    }
+
+    /// A cosine-weighted direction in the hemisphere around `normal`.
+    fn cosine_hemisphere(&mut self, normal: &Vec3) -> Vec3 {
+        // Rejection-sample a unit-sphere direction, then bias toward `normal`.
+        loop {
+            let p = Vec3::new(
+                self.next_f32() * 2.0 - 1.0,
+                self.next_f32() * 2.0 - 1.0,
+                self.next_f32() * 2.0 - 1.0,
+            );
+            let len2 = dot(&p, &p);
+            if len2 > 1e-6 && len2 <= 1.0 {
+                let dir = normalize(&p) + *normal;
+                return normalize(&dir);
+            }
+        }
+    }
+}
+
+/// A ray `origin + t * dir`.
+#[derive(Clone, Copy, Debug)]
+pub struct Ray {
+    pub origin: Vec3,
+    pub dir: Vec3,
+}
+
+impl Ray {
+    pub fn new(origin: Vec3, dir: Vec3) -> Self {
+        Self { origin, dir }
+    }
+
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.origin + self.dir * t
+    }
+}
+
+/// An axis-aligned bounding box.
+#[derive(Clone, Copy, Debug)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn empty() -> Self {
+        Self {
+            min: Vec3::new(f32::MAX, f32::MAX, f32::MAX),
+            max: Vec3::new(f32::MIN, f32::MIN, f32::MIN),
+        }
+    }
+
+    /// Grows the box to contain `p`.
+    pub fn grow(&mut self, p: &Vec3) {
+        self.min = Vec3::new(self.min.x.min(p.x), self.min.y.min(p.y), self.min.z.min(p.z));
+        self.max = Vec3::new(self.max.x.max(p.x), self.max.y.max(p.y), self.max.z.max(p.z));
+    }
+
+    /// Unions two boxes.
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb {
+            min: Vec3::new(
+                self.min.x.min(other.min.x),
+                self.min.y.min(other.min.y),
+                self.min.z.min(other.min.z),
+            ),
+            max: Vec3::new(
+                self.max.x.max(other.max.x),
+                self.max.y.max(other.max.y),
+                self.max.z.max(other.max.z),
+            ),
+        }
+    }
+
+    /// Slab test. Returns the entry/exit parameters when the ray overlaps the
+    /// box within `[t_min, t_max]`.
+    pub fn hit(&self, ray: &Ray, mut t_min: f32, mut t_max: f32) -> Option<(f32, f32)> {
+        for axis in 0..3 {
+            let (o, d, lo, hi) = match axis {
+                0 => (ray.origin.x, ray.dir.x, self.min.x, self.max.x),
+                1 => (ray.origin.y, ray.dir.y, self.min.y, self.max.y),
+                _ => (ray.origin.z, ray.dir.z, self.min.z, self.max.z),
+            };
+            let inv = 1.0 / d;
+            let mut t0 = (lo - o) * inv;
+            let mut t1 = (hi - o) * inv;
+            if inv < 0.0 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            t_min = t_min.max(t0);
+            t_max = t_max.min(t1);
+            // A zero-thickness box (around an axis-aligned triangle) gives
+            // t_max == t_min and still counts as overlapping.
+            if t_max < t_min {
+                return None;
+            }
+        }
+        Some((t_min, t_max))
+    }
+}
+
+/// A scene triangle with an index into the scene's material table.
+#[derive(Clone, Copy, Debug)]
+pub struct Triangle {
+    pub a: Vec3,
+    pub b: Vec3,
+    pub c: Vec3,
+    pub material: usize,
+}
+
+impl Triangle {
+    fn bounds(&self) -> Aabb {
+        let mut bb = Aabb::empty();
+        bb.grow(&self.a);
+        bb.grow(&self.b);
+        bb.grow(&self.c);
+        bb
+    }
+
+    fn centroid(&self) -> Vec3 {
+        (self.a + self.b + self.c) * (1.0 / 3.0)
+    }
+
+    /// Möller–Trumbore intersection returning barycentric `(u, v)` and `t`.
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<Hit> {
+        let edge1 = self.b - self.a;
+        let edge2 = self.c - self.a;
+        let pvec = cross(&ray.dir, &edge2);
+        let det = dot(&edge1, &pvec);
+        if det.abs() < 1e-8 {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+        let tvec = ray.origin - self.a;
+        let u = dot(&tvec, &pvec) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let qvec = cross(&tvec, &edge1);
+        let v = dot(&ray.dir, &qvec) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = dot(&edge2, &qvec) * inv_det;
+        if t < t_min || t > t_max {
+            return None;
+        }
+
+        let mut normal = normalize(&cross(&edge1, &edge2));
+        let front_face = dot(&ray.dir, &normal) < 0.0;
+        if !front_face {
+            normal = normal * -1.0;
+        }
+
+        Some(Hit {
+            t,
+            point: ray.at(t),
+            normal,
+            u,
+            v,
+            front_face,
+            material: self.material,
+        })
+    }
+}
+
+/// The result of a ray–triangle intersection.
+#[derive(Clone, Copy, Debug)]
+pub struct Hit {
+    pub t: f32,
+    pub point: Vec3,
+    pub normal: Vec3,
+    pub u: f32,
+    pub v: f32,
+    pub front_face: bool,
+    pub material: usize,
+}
+
+/// A node in the flat [`Bvh`] array. A node is a leaf when `tri_count > 0`
+/// (covering `tris[first..first + tri_count]`); otherwise its children live at
+/// `left_child` and `right_child`. The depth-first build order means the right
+/// child is not adjacent to the left whenever the left subtree is internal, so
+/// both indices are stored explicitly.
+#[derive(Clone, Copy, Debug)]
+struct BvhNode {
+    bbox: Aabb,
+    left_child: u32,
+    right_child: u32,
+    first: u32,
+    tri_count: u32,
+}
+
+/// A bounding volume hierarchy over a triangle list, built by recursively
+/// splitting along the longest axis of the centroid bounds.
+pub struct Bvh {
+    nodes: Vec<BvhNode>,
+    /// Triangle indices permuted so each leaf owns a contiguous range.
+    indices: Vec<u32>,
+}
+
+impl Bvh {
+    pub fn build(triangles: &[Triangle]) -> Self {
+        let mut indices: Vec<u32> = (0..triangles.len() as u32).collect();
+        let mut nodes = Vec::with_capacity(triangles.len().max(1) * 2);
+        if !triangles.is_empty() {
+            Self::build_node(triangles, &mut indices, 0, triangles.len(), &mut nodes);
+        }
+        Self { nodes, indices }
+    }
+
+    fn build_node(
+        triangles: &[Triangle],
+        indices: &mut [u32],
+        start: usize,
+        end: usize,
+        nodes: &mut Vec<BvhNode>,
+    ) -> u32 {
+        let node_index = nodes.len() as u32;
+
+        let mut bbox = Aabb::empty();
+        let mut centroid_bounds = Aabb::empty();
+        for &i in &indices[start..end] {
+            bbox = bbox.union(&triangles[i as usize].bounds());
+            centroid_bounds.grow(&triangles[i as usize].centroid());
+        }
+
+        // Reserve this node's slot up front so children get later indices.
+        nodes.push(BvhNode {
+            bbox,
+            left_child: 0,
+            right_child: 0,
+            first: start as u32,
+            tri_count: 0,
+        });
+
+        let count = end - start;
+        if count <= 2 {
+            nodes[node_index as usize].tri_count = count as u32;
+            return node_index;
+        }
+
+        // Split along the longest centroid axis at its median.
+        let extent = centroid_bounds.max - centroid_bounds.min;
+        let axis = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+
+        let key = |t: &Triangle| {
+            let c = t.centroid();
+            match axis {
+                0 => c.x,
+                1 => c.y,
+                _ => c.z,
+            }
+        };
+
+        indices[start..end].sort_by(|&l, &r| {
+            key(&triangles[l as usize])
+                .partial_cmp(&key(&triangles[r as usize]))
+                .unwrap()
+        });
+        let mid = start + count / 2;
+
+        let left = Self::build_node(triangles, indices, start, mid, nodes);
+        let right = Self::build_node(triangles, indices, mid, end, nodes);
+        // The depth-first build places the whole left subtree between this node
+        // and the right child, so record both indices explicitly.
+        nodes[node_index as usize].left_child = left;
+        nodes[node_index as usize].right_child = right;
+        node_index
+    }
+
+    /// Returns the nearest hit along `ray`, if any.
+    pub fn hit(&self, triangles: &[Triangle], ray: &Ray, t_min: f32, t_max: f32) -> Option<Hit> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let mut closest = t_max;
+        let mut best: Option<Hit> = None;
+        // Explicit stack avoids recursion per ray.
+        let mut stack = [0u32; 64];
+        let mut sp = 0usize;
+        stack[sp] = 0;
+        sp += 1;
+
+        while sp > 0 {
+            sp -= 1;
+            let node = &self.nodes[stack[sp] as usize];
+            if node.bbox.hit(ray, t_min, closest).is_none() {
+                continue;
+            }
+            if node.tri_count > 0 {
+                for k in 0..node.tri_count {
+                    let tri = &triangles[self.indices[(node.first + k) as usize] as usize];
+                    if let Some(hit) = tri.hit(ray, t_min, closest) {
+                        closest = hit.t;
+                        best = Some(hit);
+                    }
+                }
+            } else {
+                stack[sp] = node.left_child;
+                sp += 1;
+                stack[sp] = node.right_child;
+                sp += 1;
+            }
+        }
+
+        best
+    }
+}
+
+/// How a surface interacts with an incoming ray. `scatter` returns the
+/// attenuation and bounced ray, or `None` when the ray is absorbed; `emitted`
+/// adds light to the path (zero for non-emitters).
+pub trait Material {
+    fn scatter(&self, ray: &Ray, hit: &Hit, rng: &mut Rng) -> Option<(Vec3, Ray)>;
+
+    fn emitted(&self) -> Vec3 {
+        Vec3::new(0.0, 0.0, 0.0)
+    }
+
+    /// A representative flat colour for backends that do not evaluate the full
+    /// BRDF, such as the scanline `RasterBackend`.
+    fn base_color(&self) -> Vec4 {
+        Vec4::new(1.0, 1.0, 1.0, 1.0)
+    }
+}
+
+/// Matte surface scattering cosine-weighted over the hemisphere.
+pub struct Lambertian {
+    pub albedo: Vec3,
+}
+
+impl Material for Lambertian {
+    fn scatter(&self, _ray: &Ray, hit: &Hit, rng: &mut Rng) -> Option<(Vec3, Ray)> {
+        let dir = rng.cosine_hemisphere(&hit.normal);
+        Some((self.albedo, Ray::new(hit.point, dir)))
+    }
+
+    fn base_color(&self) -> Vec4 {
+        Vec4::new(self.albedo.x, self.albedo.y, self.albedo.z, 1.0)
+    }
+}
+
+/// A perfect mirror; `fuzz` perturbs the reflection for a brushed look.
+pub struct Metal {
+    pub albedo: Vec3,
+    pub fuzz: f32,
+}
+
+impl Material for Metal {
+    fn scatter(&self, ray: &Ray, hit: &Hit, rng: &mut Rng) -> Option<(Vec3, Ray)> {
+        let reflected = reflect(&normalize(&ray.dir), &hit.normal);
+        let fuzzed = reflected + rng.cosine_hemisphere(&hit.normal) * self.fuzz;
+        if dot(&fuzzed, &hit.normal) > 0.0 {
+            Some((self.albedo, Ray::new(hit.point, fuzzed)))
+        } else {
+            None
+        }
+    }
+
+    fn base_color(&self) -> Vec4 {
+        Vec4::new(self.albedo.x, self.albedo.y, self.albedo.z, 1.0)
+    }
+}
+
+/// Dielectric (glass) using Snell refraction with a Schlick Fresnel term.
+pub struct Dielectric {
+    pub ior: f32,
+}
+
+impl Dielectric {
+    fn schlick(cosine: f32, ref_idx: f32) -> f32 {
+        let r0 = ((1.0 - ref_idx) / (1.0 + ref_idx)).powi(2);
+        r0 + (1.0 - r0) * (1.0 - cosine).powi(5)
+    }
+}
+
+impl Material for Dielectric {
+    fn scatter(&self, ray: &Ray, hit: &Hit, rng: &mut Rng) -> Option<(Vec3, Ray)> {
+        let ratio = if hit.front_face { 1.0 / self.ior } else { self.ior };
+        let unit = normalize(&ray.dir);
+        let cos_theta = (-dot(&unit, &hit.normal)).min(1.0);
+        let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+
+        let cannot_refract = ratio * sin_theta > 1.0;
+        let dir = if cannot_refract || Self::schlick(cos_theta, ratio) > rng.next_f32() {
+            reflect(&unit, &hit.normal)
+        } else {
+            refract(&unit, &hit.normal, ratio)
+        };
+
+        Some((Vec3::new(1.0, 1.0, 1.0), Ray::new(hit.point, dir)))
+    }
+}
+
+/// A constant-colour emitter that injects light into the scene.
+pub struct Emissive {
+    pub radiance: Vec3,
+}
+
+impl Material for Emissive {
+    fn scatter(&self, _ray: &Ray, _hit: &Hit, _rng: &mut Rng) -> Option<(Vec3, Ray)> {
+        None
+    }
+
+    fn emitted(&self) -> Vec3 {
+        self.radiance
+    }
+
+    fn base_color(&self) -> Vec4 {
+        Vec4::new(self.radiance.x, self.radiance.y, self.radiance.z, 1.0)
+    }
+}
+
+/// Scene geometry plus the material table its triangles index into.
+pub struct Scene {
+    pub triangles: Vec<Triangle>,
+    pub materials: Vec<Box<dyn Material>>,
+    bvh: Bvh,
+}
+
+impl Scene {
+    pub fn new(triangles: Vec<Triangle>, materials: Vec<Box<dyn Material>>) -> Self {
+        let bvh = Bvh::build(&triangles);
+        Self {
+            triangles,
+            materials,
+            bvh,
+        }
+    }
+
+    fn hit(&self, ray: &Ray) -> Option<Hit> {
+        self.bvh.hit(&self.triangles, ray, 1e-3, f32::MAX)
+    }
+}
+
+/// Pinhole camera used to generate primary rays.
+pub struct PinholeCamera {
+    origin: Vec3,
+    lower_left: Vec3,
+    horizontal: Vec3,
+    vertical: Vec3,
+}
+
+impl PinholeCamera {
+    pub fn new(look_from: Vec3, look_at: Vec3, up: Vec3, vfov_deg: f32, aspect: f32) -> Self {
+        let theta = vfov_deg.to_radians();
+        let half_height = (theta * 0.5).tan();
+        let half_width = aspect * half_height;
+
+        let w = normalize(&(look_from - look_at));
+        let u = normalize(&cross(&up, &w));
+        let v = cross(&w, &u);
+
+        Self {
+            origin: look_from,
+            lower_left: look_from - u * half_width - v * half_height - w,
+            horizontal: u * (2.0 * half_width),
+            vertical: v * (2.0 * half_height),
+        }
+    }
+
+    fn ray(&self, s: f32, t: f32) -> Ray {
+        let dir = self.lower_left + self.horizontal * s + self.vertical * t - self.origin;
+        Ray::new(self.origin, normalize(&dir))
+    }
+}
+
+/// The path-tracing backend. Mirrors [`Renderer`](crate::renderer::Renderer)'s
+/// role: it owns a [`ColorAttachment`] and writes the traced image into it.
+pub struct PathTracer {
+    color_attachment: ColorAttachment,
+    camera: PinholeCamera,
+    /// Jittered primary rays per pixel.
+    pub samples_per_pixel: u32,
+    /// Hard recursion cap backing the Russian-roulette termination.
+    pub max_depth: u32,
+}
+
+impl PathTracer {
+    pub fn new(w: u32, h: u32, camera: PinholeCamera) -> Self {
+        Self {
+            color_attachment: ColorAttachment::new(w, h),
+            camera,
+            samples_per_pixel: 16,
+            max_depth: 32,
+        }
+    }
+
+    pub fn get_rendered_image(&self) -> &[u8] {
+        self.color_attachment.data()
+    }
+
+    /// Traces the whole frame into the colour attachment.
+    pub fn render(&mut self, scene: &Scene) {
+        let w = self.color_attachment.width();
+        let h = self.color_attachment.height();
+
+        for y in 0..h {
+            for x in 0..w {
+                let mut rng = Rng::new((y as u64) * (w as u64) + x as u64 + 1);
+                let mut acc = Vec3::new(0.0, 0.0, 0.0);
+                for _ in 0..self.samples_per_pixel {
+                    let s = (x as f32 + rng.next_f32()) / (w as f32 - 1.0);
+                    // Flip vertically so row 0 is the top of the image.
+                    let t = ((h - 1 - y) as f32 + rng.next_f32()) / (h as f32 - 1.0);
+                    let ray = self.camera.ray(s, t);
+                    acc = acc + self.radiance(scene, &ray, &mut rng);
+                }
+
+                let scale = 1.0 / self.samples_per_pixel as f32;
+                // Accumulate, average and apply a gamma-2 transfer.
+                let color = Vec4::new(
+                    (acc.x * scale).sqrt().clamp(0.0, 1.0),
+                    (acc.y * scale).sqrt().clamp(0.0, 1.0),
+                    (acc.z * scale).sqrt().clamp(0.0, 1.0),
+                    1.0,
+                );
+                self.color_attachment.set(x, y, &color);
+            }
+        }
+    }
+
+    /// Iterative radiance estimate with Russian-roulette path termination.
+    fn radiance(&self, scene: &Scene, ray: &Ray, rng: &mut Rng) -> Vec3 {
+        let mut throughput = Vec3::new(1.0, 1.0, 1.0);
+        let mut radiance = Vec3::new(0.0, 0.0, 0.0);
+        let mut current = *ray;
+
+        for depth in 0..self.max_depth {
+            match scene.hit(&current) {
+                None => {
+                    // Sky term: a simple vertical gradient lights the scene.
+                    radiance = radiance + mul(&throughput, &sky(&current.dir));
+                    break;
+                }
+                Some(hit) => {
+                    let material = &scene.materials[hit.material];
+                    radiance = radiance + mul(&throughput, &material.emitted());
+
+                    match material.scatter(&current, &hit, rng) {
+                        None => break,
+                        Some((attenuation, scattered)) => {
+                            throughput = mul(&throughput, &attenuation);
+                            current = scattered;
+                        }
+                    }
+                }
+            }
+
+            // Russian roulette once the path has a few bounces in.
+            if depth > 3 {
+                let p = throughput.x.max(throughput.y).max(throughput.z).clamp(0.05, 0.95);
+                if rng.next_f32() > p {
+                    break;
+                }
+                throughput = throughput * (1.0 / p);
+            }
+        }
+
+        radiance
+    }
+}
+
+/// A procedural sky: a vertical gradient from white straight down to pale blue
+/// overhead (an even blend of the two at the horizon).
+fn sky(dir: &Vec3) -> Vec3 {
+    let unit = normalize(dir);
+    let t = 0.5 * (unit.y + 1.0);
+    Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t
+}
@@ -1,7 +1,8 @@
 use crate::camera;
+use crate::depth::{DepthAttachment, DepthFunc};
 use crate::image::*;
 use crate::math;
-use crate::scanline::*;
+use crate::texture::Texture;
 
 struct Viewport {
     x: i32,
@@ -12,6 +13,8 @@ struct Viewport {
 
 pub struct Renderer {
     color_attachment: ColorAttachment,
+    depth_attachment: DepthAttachment,
+    depth_func: DepthFunc,
     camera: camera::Camera,
     viewport: Viewport,
 }
@@ -20,6 +23,8 @@ impl Renderer {
     pub fn new(w: u32, h: u32, camera: camera::Camera) -> Self {
         Self {
             color_attachment: ColorAttachment::new(w, h),
+            depth_attachment: DepthAttachment::new(w, h),
+            depth_func: DepthFunc::default(),
             camera,
             viewport: Viewport { x: 0, y: 0, w, h },
         }
@@ -29,6 +34,14 @@ impl Renderer {
         self.color_attachment.clear(color);
     }
 
+    pub fn clear_depth(&mut self, depth: f32) {
+        self.depth_attachment.clear(depth);
+    }
+
+    pub fn set_depth_func(&mut self, func: DepthFunc) {
+        self.depth_func = func;
+    }
+
     pub fn get_canva_width(&self) -> u32 {
         self.color_attachment.width()
     }
@@ -48,66 +61,128 @@ impl Renderer {
         color: &math::Vec4,
     ) {
         // 1. convert 3D coordination to Homogeneous coordinates
-        let mut vertices = vertices.map(|v| math::Vec4::from_vec3(&v, 1.0));
+        let mvp = *self.camera.get_frustum().get_mat() * *model;
+
+        let uvs = [math::Vec2::zero(); 3];
+        self.submit_triangle(&mvp, *vertices, &uvs, None, color);
+    }
+
+    /// Like [`Renderer::draw_triangle`], but maps `texture` across the triangle
+    /// using the per-vertex `uvs`. The sampled texel is always multiplied by the
+    /// flat `color` tint for simple lighting; a white tint leaves it unchanged.
+    pub fn draw_triangle_textured(
+        &mut self,
+        model: &math::Mat4,
+        vertices: &[math::Vec3; 3],
+        uvs: &[math::Vec2; 3],
+        texture: &Texture,
+        color: &math::Vec4,
+    ) {
+        let mvp = *self.camera.get_frustum().get_mat() * *model;
+        self.submit_triangle(&mvp, *vertices, uvs, Some(texture), color);
+    }
 
-        // 2. MVP transform
-        for v in &mut vertices {
-            *v = *self.camera.get_frustum().get_mat() * *model * *v;
-            *v /= v.w;
+    fn submit_triangle(
+        &mut self,
+        mvp: &math::Mat4,
+        vertices: [math::Vec3; 3],
+        uvs: &[math::Vec2; 3],
+        texture: Option<&Texture>,
+        color: &math::Vec4,
+    ) {
+        // 2. MVP transform, keeping each vertex in clip space (w intact) so the
+        //    clipper can work in homogeneous coordinates before the divide.
+        let clip = [0usize, 1, 2].map(|i| ClipVertex {
+            pos: *mvp * math::Vec4::from_vec3(&vertices[i], 1.0),
+            color: *color,
+            uv: uvs[i],
+        });
+
+        // 3. Clip the triangle against the six frustum planes. The result is a
+        //    convex polygon of 3..=9 vertices (or empty when fully culled) that
+        //    we re-triangulate as a fan.
+        let polygon = clip::clip_triangle(&clip);
+        if polygon.len() < 3 {
+            return;
         }
 
-        // 3. Viewport transform
-        let vertices = vertices.map(|v| {
-            math::Vec2::new(
-                (v.x + 1.0) * 0.5 * (self.viewport.w as f32 - 1.0) + self.viewport.x as f32,
-                self.viewport.h as f32 - (v.y + 1.0) * 0.5 * (self.viewport.h as f32 - 1.0)
+        for i in 1..polygon.len() - 1 {
+            self.rasterize_clip_triangle(
+                &[polygon[0], polygon[i], polygon[i + 1]],
+                texture,
+                color,
+            );
+        }
+    }
+
+    /// Takes a triangle of clip-space vertices, performs the perspective divide
+    /// and viewport transform, and rasterizes it (filled trapezoids + wireframe
+    /// overlay).
+    fn rasterize_clip_triangle(
+        &mut self,
+        clip: &[ClipVertex; 3],
+        texture: Option<&Texture>,
+        color: &math::Vec4,
+    ) {
+        let vertices = clip.map(|v| {
+            let inv_w = 1.0 / v.pos.w;
+            let ndc = v.pos * inv_w;
+            let pos = math::Vec2::new(
+                (ndc.x + 1.0) * 0.5 * (self.viewport.w as f32 - 1.0) + self.viewport.x as f32,
+                self.viewport.h as f32 - (ndc.y + 1.0) * 0.5 * (self.viewport.h as f32 - 1.0)
                     + self.viewport.y as f32,
-            )
+            );
+            Vertex::new(pos, ndc.z, inv_w, v.color, v.uv)
         });
 
-
-        // 4. split triangle into trapeziods
+        // split triangle into trapeziods and rasterize them
         let [trap1, trap2] = &mut Trapezoid::from_triangle(&vertices);
-
-        // 6. rasterization trapeziods
         if let Some(trap) = trap1 {
-            self.draw_trapezoid(trap, color);
+            self.draw_trapezoid(trap, texture);
         }
         if let Some(trap) = trap2 {
-            self.draw_trapezoid(trap, color);
+            self.draw_trapezoid(trap, texture);
         }
 
-
         for i in 0..vertices.len() {
-            let p1 = &vertices[i];
-            let p2 = &vertices[(i + 1) % vertices.len()];
+            let p1 = &vertices[i].pos;
+            let p2 = &vertices[(i + 1) % vertices.len()].pos;
 
             self.draw_line(p1, p2, color);
         }
     }
 
-    fn draw_trapezoid(&mut self, trap: &Trapezoid, color: &math::Vec4) {
+    fn draw_trapezoid(&mut self, trap: &Trapezoid, texture: Option<&Texture>) {
         let top = (trap.top.ceil().max(0.0)) as i32;
         let bottom =
             (trap.bottom.ceil()).min(self.color_attachment.height() as f32 - 1.0) as i32 - 1;
         let mut y = top as f32;
 
         while y <= bottom as f32 {
-            let mut scanline = Scanline::from_trapezoid(&trap, y);
-            self.draw_scanline(&mut scanline, color);
+            let mut scanline = Scanline::from_trapezoid(trap, y);
+            self.draw_scanline(&mut scanline, texture);
             y += 1.0;
         }
     }
 
-    fn draw_scanline(&mut self, scanline: &mut Scanline, color: &math::Vec4) {
+    fn draw_scanline(&mut self, scanline: &mut Scanline, texture: Option<&Texture>) {
         let vertex = &mut scanline.vertex;
         let y = scanline.y as u32;
         while scanline.width > 0.0 {
-            let x = vertex.x;
+            let x = vertex.pos.x;
 
             if x >= 0.0 && x < self.color_attachment.width() as f32 {
                 let x = x as u32;
-                self.color_attachment.set(x, y, &color)
+                if self.depth_func.test(vertex.z, self.depth_attachment.get(x, y)) {
+                    self.depth_attachment.set(x, y, vertex.z);
+                    // Tint the texel by the interpolated vertex colour, or use
+                    // the colour directly for untextured triangles.
+                    let color = match texture {
+                        Some(tex) => mul_vec4(&tex.sample(&vertex.uv()), &vertex.color()),
+                        None => vertex.color(),
+                    };
+                    self.color_attachment.set(x, y, &color);
+                }
             }
 
             scanline.width -= 1.0;
@@ -115,6 +190,53 @@ impl Renderer {
         }
     }
 
+    /// Draws a stippled line, resetting the dash phase at the start of the
+    /// segment. Use [`Renderer::draw_line_styled_phased`] to keep the phase
+    /// continuous across a multi-segment polyline.
+    pub fn draw_line_styled(
+        &mut self,
+        p1: &math::Vec2,
+        p2: &math::Vec2,
+        color: &math::Vec4,
+        style: &LineStyle,
+    ) {
+        let mut phase = 0;
+        self.draw_line_styled_phased(p1, p2, color, style, &mut phase);
+    }
+
+    /// As [`Renderer::draw_line_styled`], but threads a caller-owned pixel
+    /// counter so the on/off phase stays continuous across several segments.
+    pub fn draw_line_styled_phased(
+        &mut self,
+        p1: &math::Vec2,
+        p2: &math::Vec2,
+        color: &math::Vec4,
+        style: &LineStyle,
+        phase: &mut u32,
+    ) {
+        let clip_result = cohen_sutherland::cohen_sutherland_line_clip(
+            p1,
+            p2,
+            &math::Vec2::zero(),
+            &math::Vec2::new(
+                self.color_attachment.width() as f32 - 1.0,
+                self.color_attachment.height() as f32 - 1.0,
+            ),
+        );
+
+        if let Some((p1, p2)) = clip_result {
+            self.draw_line_styled_without_clip(
+                p1.x as i32,
+                p1.y as i32,
+                p2.x as i32,
+                p2.y as i32,
+                color,
+                style,
+                phase,
+            );
+        }
+    }
+
     pub fn draw_line(&mut self, p1: &math::Vec2, p2: &math::Vec2, color: &math::Vec4) {
         let clip_result = cohen_sutherland::cohen_sutherland_line_clip(
             p1,
@@ -167,6 +289,381 @@ impl Renderer {
             x += sx;
         }
     }
+
+    #[allow(clippy::too_many_arguments)]
+    fn draw_line_styled_without_clip(
+        &mut self,
+        x0: i32,
+        y0: i32,
+        x1: i32,
+        y1: i32,
+        color: &math::Vec4,
+        style: &LineStyle,
+        phase: &mut u32,
+    ) {
+        let mut dx = (x1 - x0).abs();
+        let mut dy = (y1 - y0).abs();
+        let mut sx = if x1 >= x0 { 1 } else { -1 };
+        let mut sy = if y1 >= y0 { 1 } else { -1 };
+        let mut x = x0;
+        let mut y = y0;
+        let steep = if dx < dy { 1 } else { -1 };
+
+        let final_x = if dx < dy { y1 } else { x1 };
+
+        if dx < dy {
+            std::mem::swap(&mut dx, &mut dy);
+            std::mem::swap(&mut x, &mut y);
+            std::mem::swap(&mut sx, &mut sy);
+        }
+
+        let mut e = -dx;
+        let step = 2 * dy;
+        let desc = -2 * dx;
+
+        while x != final_x {
+            // Only light "on" pixels; the stepper advances either way so each
+            // dash and gap covers a fixed number of steps along the major axis.
+            if style.lit(*phase) {
+                if steep > 0 {
+                    self.color_attachment.set(y as u32, x as u32, color);
+                } else {
+                    self.color_attachment.set(x as u32, y as u32, color);
+                }
+            }
+            *phase = phase.wrapping_add(1);
+
+            e += step;
+            if e >= 0 {
+                y += sy;
+                e += desc;
+            }
+            x += sx;
+        }
+    }
+}
+
+/// A stipple pattern for [`Renderer::draw_line_styled`]: over each `period`
+/// pixels the first `visible` are lit and the rest skipped. `start_on` selects
+/// whether the pattern begins in the lit run or the gap.
+#[derive(Clone, Copy, Debug)]
+pub struct LineStyle {
+    /// Total length of one on/off cycle in pixels.
+    pub period: u32,
+    /// Lit pixels at the start of each cycle.
+    pub visible: u32,
+    /// Whether pixel zero of the span is lit.
+    pub start_on: bool,
+}
+
+impl LineStyle {
+    /// A fully solid line (equivalent to [`Renderer::draw_line`]).
+    pub fn solid() -> Self {
+        Self {
+            period: 1,
+            visible: 1,
+            start_on: true,
+        }
+    }
+
+    /// A dashed pattern of `visible` lit pixels followed by `period - visible`
+    /// blank pixels.
+    pub fn dashed(period: u32, visible: u32) -> Self {
+        Self {
+            period: period.max(1),
+            visible: visible.min(period),
+            start_on: true,
+        }
+    }
+
+    /// Whether the pixel at `counter` along the span is lit.
+    fn lit(&self, counter: u32) -> bool {
+        let offset = if self.start_on { 0 } else { self.visible };
+        (counter.wrapping_add(offset)) % self.period < self.visible
+    }
+}
+
+/// A vertex still in clip space (before the perspective divide), carrying the
+/// homogeneous position and every interpolated attribute. This is the unit the
+/// frustum clipper operates on.
+#[derive(Clone, Copy, Debug)]
+struct ClipVertex {
+    pos: math::Vec4,
+    color: math::Vec4,
+    uv: math::Vec2,
+}
+
+impl ClipVertex {
+    /// Linearly interpolates position and all attributes between `a` and `b` by
+    /// `t`, used when an edge crosses a clip plane.
+    fn lerp(a: &ClipVertex, b: &ClipVertex, t: f32) -> ClipVertex {
+        ClipVertex {
+            pos: a.pos + (b.pos - a.pos) * t,
+            color: a.color + (b.color - a.color) * t,
+            uv: math::Vec2::new(
+                a.uv.x + (b.uv.x - a.uv.x) * t,
+                a.uv.y + (b.uv.y - a.uv.y) * t,
+            ),
+        }
+    }
+}
+
+/// Component-wise product of two colours (texel × tint).
+fn mul_vec4(a: &math::Vec4, b: &math::Vec4) -> math::Vec4 {
+    math::Vec4::new(a.x * b.x, a.y * b.y, a.z * b.z, a.w * b.w)
+}
+
+/// A rasterizer vertex carrying its screen position plus every attribute the
+/// scanline fill needs to interpolate. Attributes that must stay
+/// perspective-correct are stored pre-multiplied by `inv_w`; the true value is
+/// recovered per pixel by dividing the interpolated `attribute * inv_w` by the
+/// interpolated `inv_w` (see [`Vertex::color`]). `z` is the NDC depth and is
+/// interpolated linearly in screen space for the depth test.
+#[derive(Clone, Copy, Debug)]
+struct Vertex {
+    pos: math::Vec2,
+    z: f32,
+    inv_w: f32,
+    /// Vertex colour pre-multiplied by `inv_w`.
+    color: math::Vec4,
+    /// Texture coordinate pre-multiplied by `inv_w`.
+    uv: math::Vec2,
+}
+
+impl Vertex {
+    fn new(pos: math::Vec2, z: f32, inv_w: f32, color: math::Vec4, uv: math::Vec2) -> Self {
+        Self {
+            pos,
+            z,
+            inv_w,
+            color: color * inv_w,
+            uv: math::Vec2::new(uv.x * inv_w, uv.y * inv_w),
+        }
+    }
+
+    /// Recovers the perspective-correct colour at this (interpolated) vertex.
+    fn color(&self) -> math::Vec4 {
+        self.color / self.inv_w
+    }
+
+    /// Recovers the perspective-correct texture coordinate at this vertex.
+    fn uv(&self) -> math::Vec2 {
+        math::Vec2::new(self.uv.x / self.inv_w, self.uv.y / self.inv_w)
+    }
+
+    /// Linearly interpolates every field between `a` and `b` by `t`.
+    fn lerp(a: &Vertex, b: &Vertex, t: f32) -> Vertex {
+        Vertex {
+            pos: math::Vec2::new(
+                a.pos.x + (b.pos.x - a.pos.x) * t,
+                a.pos.y + (b.pos.y - a.pos.y) * t,
+            ),
+            z: a.z + (b.z - a.z) * t,
+            inv_w: a.inv_w + (b.inv_w - a.inv_w) * t,
+            color: a.color + (b.color - a.color) * t,
+            uv: math::Vec2::new(
+                a.uv.x + (b.uv.x - a.uv.x) * t,
+                a.uv.y + (b.uv.y - a.uv.y) * t,
+            ),
+        }
+    }
+
+    /// The per-pixel step in `x` to advance from `a` to `b` over `width`
+    /// horizontal pixels.
+    fn step(a: &Vertex, b: &Vertex, width: f32) -> Vertex {
+        let inv = 1.0 / width;
+        Vertex {
+            pos: math::Vec2::new((b.pos.x - a.pos.x) * inv, (b.pos.y - a.pos.y) * inv),
+            z: (b.z - a.z) * inv,
+            inv_w: (b.inv_w - a.inv_w) * inv,
+            color: (b.color - a.color) * inv,
+            uv: math::Vec2::new((b.uv.x - a.uv.x) * inv, (b.uv.y - a.uv.y) * inv),
+        }
+    }
+}
+
+impl std::ops::AddAssign for Vertex {
+    fn add_assign(&mut self, rhs: Vertex) {
+        self.pos.x += rhs.pos.x;
+        self.pos.y += rhs.pos.y;
+        self.z += rhs.z;
+        self.inv_w += rhs.inv_w;
+        self.color = self.color + rhs.color;
+        self.uv.x += rhs.uv.x;
+        self.uv.y += rhs.uv.y;
+    }
+}
+
+/// One edge of a [`Trapezoid`], described by its upper and lower vertex. The
+/// fill interpolates a [`Vertex`] along it for any scanline `y`.
+#[derive(Clone, Copy, Debug)]
+struct Edge {
+    top: Vertex,
+    bottom: Vertex,
+}
+
+impl Edge {
+    /// Interpolates the edge's vertex at absolute scanline `y`.
+    fn vertex_at(&self, y: f32) -> Vertex {
+        let t = (y - self.top.pos.y) / (self.bottom.pos.y - self.top.pos.y);
+        Vertex::lerp(&self.top, &self.bottom, t)
+    }
+}
+
+/// A flat-top/flat-bottom trapezoid: the unit a triangle is split into so each
+/// scanline is bounded by a left and a right edge.
+#[derive(Clone, Copy, Debug)]
+struct Trapezoid {
+    top: f32,
+    bottom: f32,
+    left: Edge,
+    right: Edge,
+}
+
+impl Trapezoid {
+    /// Splits a triangle into at most two trapezoids, ordered top to bottom.
+    /// Either slot is `None` when the corresponding half is degenerate.
+    fn from_triangle(vertices: &[Vertex; 3]) -> [Option<Trapezoid>; 2] {
+        let mut v = *vertices;
+        v.sort_by(|a, b| a.pos.y.partial_cmp(&b.pos.y).unwrap());
+        let [top, mid, bottom] = v;
+
+        // Degenerate triangle collapsed onto a single scanline.
+        if top.pos.y == bottom.pos.y {
+            return [None, None];
+        }
+
+        // The long edge spans top..bottom; decide which side `mid` lands on by
+        // comparing x at mid's height.
+        let t = (mid.pos.y - top.pos.y) / (bottom.pos.y - top.pos.y);
+        let split_x = top.pos.x + (bottom.pos.x - top.pos.x) * t;
+
+        let long = Edge {
+            top,
+            bottom,
+        };
+
+        if split_x <= mid.pos.x {
+            // `mid` is on the right.
+            [
+                Trapezoid::between(top.pos.y, mid.pos.y, Edge { top, bottom }, Edge { top, bottom: mid }),
+                Trapezoid::between(mid.pos.y, bottom.pos.y, long, Edge { top: mid, bottom }),
+            ]
+        } else {
+            // `mid` is on the left.
+            [
+                Trapezoid::between(top.pos.y, mid.pos.y, Edge { top, bottom: mid }, Edge { top, bottom }),
+                Trapezoid::between(mid.pos.y, bottom.pos.y, Edge { top: mid, bottom }, long),
+            ]
+        }
+    }
+
+    fn between(top: f32, bottom: f32, left: Edge, right: Edge) -> Option<Trapezoid> {
+        if top >= bottom {
+            None
+        } else {
+            Some(Trapezoid {
+                top,
+                bottom,
+                left,
+                right,
+            })
+        }
+    }
+}
+
+/// The interpolated span for a single scanline: the left-most [`Vertex`], the
+/// per-pixel `step`, the covered `width` in pixels and the row `y`.
+#[derive(Clone, Copy, Debug)]
+struct Scanline {
+    vertex: Vertex,
+    step: Vertex,
+    y: f32,
+    width: f32,
+}
+
+impl Scanline {
+    fn from_trapezoid(trap: &Trapezoid, y: f32) -> Scanline {
+        let left = trap.left.vertex_at(y);
+        let right = trap.right.vertex_at(y);
+        let width = (right.pos.x - left.pos.x).max(0.0).ceil();
+        let step = if width > 0.0 {
+            Vertex::step(&left, &right, right.pos.x - left.pos.x)
+        } else {
+            left
+        };
+        Scanline {
+            vertex: left,
+            step,
+            y,
+            width,
+        }
+    }
+}
+
+/// Homogeneous-space triangle clipping against the view frustum using the
+/// [Sutherland–Hodgman algorithm](https://en.wikipedia.org/wiki/Sutherland%E2%80%93Hodgman_algorithm).
+///
+/// Clipping happens on the post-MVP [`ClipVertex`] list, before the perspective
+/// divide, so geometry with `w <= 0` (crossing or behind the camera plane) no
+/// longer produces garbage screen coordinates.
+mod clip {
+    use super::ClipVertex;
+
+    /// Signed distance of a clip-space vertex to a frustum plane. A vertex is
+    /// kept when its distance is `>= 0`.
+    type Plane = fn(&ClipVertex) -> f32;
+
+    /// The six canonical clip planes `-w <= {x,y,z} <= w`. The last pair forms
+    /// the near/far bounds on `z`, which also guards the `w >= 0` half-space.
+    const PLANES: [Plane; 6] = [
+        |v| v.pos.w + v.pos.x, // left:   x >= -w
+        |v| v.pos.w - v.pos.x, // right:  x <=  w
+        |v| v.pos.w + v.pos.y, // bottom: y >= -w
+        |v| v.pos.w - v.pos.y, // top:    y <=  w
+        |v| v.pos.w + v.pos.z, // near:   z >= -w
+        |v| v.pos.w - v.pos.z, // far:    z <=  w
+    ];
+
+    /// Clips a triangle, returning the resulting convex polygon as a vertex
+    /// list (3..=9 vertices, or empty when fully outside the frustum).
+    pub fn clip_triangle(tri: &[ClipVertex; 3]) -> Vec<ClipVertex> {
+        let mut polygon = tri.to_vec();
+
+        for plane in PLANES.iter() {
+            if polygon.len() < 3 {
+                break;
+            }
+            polygon = clip_against_plane(&polygon, *plane);
+        }
+
+        polygon
+    }
+
+    fn clip_against_plane(polygon: &[ClipVertex], plane: Plane) -> Vec<ClipVertex> {
+        let mut output = Vec::with_capacity(polygon.len() + 1);
+
+        for i in 0..polygon.len() {
+            let curr = &polygon[i];
+            let next = &polygon[(i + 1) % polygon.len()];
+
+            let d0 = plane(curr);
+            let d1 = plane(next);
+            let curr_in = d0 >= 0.0;
+            let next_in = d1 >= 0.0;
+
+            if curr_in {
+                output.push(*curr);
+            }
+            // Emit the intersection whenever the edge straddles the plane.
+            if curr_in != next_in {
+                let t = d0 / (d0 - d1);
+                output.push(ClipVertex::lerp(curr, next, t));
+            }
+        }
+
+        output
+    }
 }
 
 /// [Cohen-Sutherland Algorithm](https://en.wikipedia.org/wiki/Cohen%E2%80%93Sutherland_algorithm)
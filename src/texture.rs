@@ -0,0 +1,148 @@
+//! Image textures sampled during scanline fill. A [`Texture`] wraps an RGBA
+//! buffer and is sampled in normalized `(u, v)` space with a selectable
+//! [`FilterMode`] and [`WrapMode`].
+
+use crate::math::{Vec2, Vec4};
+
+/// How a texel is reconstructed between sample points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterMode {
+    /// Pick the nearest texel (blocky, cheap).
+    Nearest,
+    /// Blend the four nearest texels by the fractional coordinate.
+    Bilinear,
+}
+
+/// How coordinates outside `[0, 1]` are mapped back into the texture.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WrapMode {
+    /// Tile the texture (`coord - coord.floor()`).
+    Repeat,
+    /// Clamp to the edge texel.
+    Clamp,
+    /// Tile with every other copy mirrored.
+    Mirror,
+}
+
+/// An RGBA image plus its sampling settings.
+pub struct Texture {
+    width: u32,
+    height: u32,
+    /// Row-major RGBA bytes, length `width * height * 4`.
+    data: Vec<u8>,
+    pub filter: FilterMode,
+    pub wrap: WrapMode,
+}
+
+impl Texture {
+    pub fn new(width: u32, height: u32, data: Vec<u8>) -> Self {
+        assert_eq!(data.len(), (width * height * 4) as usize);
+        Self {
+            width,
+            height,
+            data,
+            filter: FilterMode::Nearest,
+            wrap: WrapMode::Repeat,
+        }
+    }
+
+    pub fn with_modes(mut self, filter: FilterMode, wrap: WrapMode) -> Self {
+        self.filter = filter;
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Samples the texture at `uv`, returning a linear RGBA colour in `[0, 1]`.
+    pub fn sample(&self, uv: &Vec2) -> Vec4 {
+        let u = self.wrap_coord(uv.x);
+        let v = self.wrap_coord(uv.y);
+
+        match self.filter {
+            FilterMode::Nearest => {
+                let x = ((u * self.width as f32) as i32).clamp(0, self.width as i32 - 1);
+                let y = ((v * self.height as f32) as i32).clamp(0, self.height as i32 - 1);
+                self.texel(x, y)
+            }
+            FilterMode::Bilinear => {
+                // Sample at texel centres so the blend weights are symmetric.
+                let fx = u * self.width as f32 - 0.5;
+                let fy = v * self.height as f32 - 0.5;
+                let x0 = fx.floor() as i32;
+                let y0 = fy.floor() as i32;
+                let tx = fx - x0 as f32;
+                let ty = fy - y0 as f32;
+
+                let c00 = self.texel(x0, y0);
+                let c10 = self.texel(x0 + 1, y0);
+                let c01 = self.texel(x0, y0 + 1);
+                let c11 = self.texel(x0 + 1, y0 + 1);
+
+                let top = lerp(&c00, &c10, tx);
+                let bottom = lerp(&c01, &c11, tx);
+                lerp(&top, &bottom, ty)
+            }
+        }
+    }
+
+    /// Fetches a single texel, mapping the pixel coordinate back into the image
+    /// bounds per the wrap mode so neighbouring taps tile seamlessly.
+    fn texel(&self, x: i32, y: i32) -> Vec4 {
+        let x = self.wrap_texel(x, self.width);
+        let y = self.wrap_texel(y, self.height);
+        let i = ((y * self.width + x) * 4) as usize;
+        Vec4::new(
+            self.data[i] as f32 / 255.0,
+            self.data[i + 1] as f32 / 255.0,
+            self.data[i + 2] as f32 / 255.0,
+            self.data[i + 3] as f32 / 255.0,
+        )
+    }
+
+    /// Maps an integer texel coordinate into `[0, n)` per the wrap mode, so the
+    /// four bilinear taps wrap consistently with the centre coordinate.
+    fn wrap_texel(&self, c: i32, n: u32) -> u32 {
+        let n = n as i32;
+        match self.wrap {
+            WrapMode::Repeat => c.rem_euclid(n) as u32,
+            WrapMode::Clamp => c.clamp(0, n - 1) as u32,
+            WrapMode::Mirror => {
+                let period = 2 * n;
+                let m = c.rem_euclid(period);
+                (if m >= n { period - 1 - m } else { m }) as u32
+            }
+        }
+    }
+
+    /// Maps an arbitrary coordinate into `[0, 1]` per the wrap mode.
+    fn wrap_coord(&self, c: f32) -> f32 {
+        match self.wrap {
+            WrapMode::Repeat => c - c.floor(),
+            WrapMode::Clamp => c.clamp(0.0, 1.0),
+            WrapMode::Mirror => {
+                let t = (c.rem_euclid(2.0)).abs();
+                if t > 1.0 {
+                    2.0 - t
+                } else {
+                    t
+                }
+            }
+        }
+    }
+}
+
+fn lerp(a: &Vec4, b: &Vec4, t: f32) -> Vec4 {
+    Vec4::new(
+        a.x + (b.x - a.x) * t,
+        a.y + (b.y - a.y) * t,
+        a.z + (b.z - a.z) * t,
+        a.w + (b.w - a.w) * t,
+    )
+}